@@ -1,77 +1,514 @@
+use cpu::debugger::{Debuggable, StepEvent};
 use cpu::flag_register::FlagRegister;
+use cpu::interrupt::{Interrupt, IE_ADDRESS, IF_ADDRESS};
 use cpu::opcodes::*;
 use cpu::registers::Registers;
+use memory::Bus;
+
+// Servicing an interrupt takes 5 M-cycles: two wasted cycles, a two-cycle
+// push of `pc` and a one-cycle jump to the vector.
+const INTERRUPT_SERVICE_CYCLES: u8 = 20;
 
 #[derive(Debug)]
 pub struct CPU {
     pub registers: Registers,
+    pub pc: u16,
+    pub sp: u16,
+    // Running total of T-cycles consumed since reset, for a scheduler to
+    // drive the PPU/timer/audio in lockstep with the CPU.
+    pub cycles: u64,
+    // Interrupt master enable. Gates whether a pending, individually-enabled
+    // interrupt is actually serviced.
+    pub ime: bool,
+    // Set by HALT; fetching stays suspended until an interrupt is pending.
+    pub halted: bool,
+    // Counts down the one-instruction delay between `EI` executing and
+    // `ime` actually turning on, so the instruction right after `EI` still
+    // runs with interrupts masked.
+    ei_delay: u8,
+    // `pc` addresses that stop `step_debug` before the instruction there runs.
+    breakpoints: Vec<u16>,
+    // Memory addresses that stop `step_debug` right after a step that
+    // changed the byte stored there.
+    watchpoints: Vec<u16>,
+}
+
+impl Default for CPU {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CPU {
-    pub fn execute(&mut self, instruction: Instruction) {
+    pub fn new() -> Self {
+        Self {
+            registers: Registers::new(),
+            pc: 0,
+            sp: 0,
+            cycles: 0,
+            ime: false,
+            halted: false,
+            ei_delay: 0,
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+        }
+    }
+
+    // Fetches the byte at `pc`, decodes it (following a 0xCB-prefix byte into
+    // the second opcode table when present), reads any immediate operand
+    // byte that follows, advances `pc` past the whole instruction and
+    // dispatches it through `execute`. Returns the number of T-cycles the
+    // instruction consumed and folds them into the running `cycles` counter.
+    //
+    // Before any of that, services the highest-priority pending interrupt if
+    // `ime` is set (consuming this whole step on its own, the way a CALL to
+    // the vector would), and holds fetching altogether while halted.
+    pub fn step(&mut self, bus: &mut Bus) -> u8 {
+        let interrupt_cycles = self.service_interrupt(bus);
+        if interrupt_cycles > 0 {
+            self.cycles = self.cycles.wrapping_add(interrupt_cycles as u64);
+            return interrupt_cycles;
+        }
+
+        if self.halted {
+            if self.interrupt_pending(bus) {
+                self.halted = false;
+            } else {
+                self.cycles = self.cycles.wrapping_add(4);
+                return 4;
+            }
+        }
+
+        let (instruction, length) = self.decode(bus);
+        self.pc = self.pc.wrapping_add(length);
+        let cycles = self.execute(bus, instruction);
+        self.cycles = self.cycles.wrapping_add(cycles as u64);
+
+        if self.ei_delay > 0 {
+            self.ei_delay -= 1;
+            if self.ei_delay == 0 {
+                self.ime = true;
+            }
+        }
+
+        cycles
+    }
+
+    fn interrupt_pending(&self, bus: &Bus) -> bool {
+        bus.read(IE_ADDRESS) & bus.read(IF_ADDRESS) != 0
+    }
+
+    // Clears the serviced interrupt's IF bit, disables further interrupts,
+    // pushes the current `pc` and jumps to the interrupt's fixed vector.
+    // Returns the number of T-cycles spent, or 0 if nothing was serviced.
+    fn service_interrupt(&mut self, bus: &mut Bus) -> u8 {
+        if !self.ime {
+            return 0;
+        }
+
+        let ie = bus.read(IE_ADDRESS);
+        let iflag = bus.read(IF_ADDRESS);
+        match Interrupt::highest_priority(ie, iflag) {
+            Some(interrupt) => {
+                bus.write(IF_ADDRESS, iflag & !interrupt.mask());
+                self.ime = false;
+                self.halted = false;
+                self.push_word(bus, self.pc);
+                self.pc = interrupt.vector();
+                INTERRUPT_SERVICE_CYCLES
+            }
+            None => 0,
+        }
+    }
+
+    fn decode(&self, bus: &Bus) -> (Instruction, u16) {
+        self.decode_at(bus, self.pc)
+    }
+
+    // Same as `decode`, but reads from an arbitrary address instead of `pc`,
+    // so a disassembly listing can decode ahead without disturbing the CPU.
+    fn decode_at(&self, bus: &Bus, addr: u16) -> (Instruction, u16) {
+        let opcode = bus.read(addr);
+        if opcode == 0xCB {
+            let cb_opcode = bus.read(addr.wrapping_add(1));
+            (Self::decode_cb(cb_opcode), 2)
+        } else {
+            self.decode_unprefixed(bus, addr, opcode)
+        }
+    }
+
+    fn decode_unprefixed(&self, bus: &Bus, addr: u16, opcode: u8) -> (Instruction, u16) {
+        let immediate = || bus.read(addr.wrapping_add(1));
+
+        match opcode {
+            0x00 => (Instruction::NOP, 1),
+
+            0x07 => (Instruction::RLCA, 1),
+            0x0F => (Instruction::RRCA, 1),
+            0x17 => (Instruction::RLA, 1),
+            0x1F => (Instruction::RRA, 1),
+            0x27 => (Instruction::DAA, 1),
+            0x76 => (Instruction::HALT, 1),
+            0xF3 => (Instruction::DI, 1),
+            0xFB => (Instruction::EI, 1),
+            0xD9 => (Instruction::RETI, 1),
+
+            0x09 => (Instruction::ADD16(WordTarget::BC), 1),
+            0x19 => (Instruction::ADD16(WordTarget::DE), 1),
+            0x29 => (Instruction::ADD16(WordTarget::HL), 1),
+            0x39 => (Instruction::ADD16(WordTarget::SP), 1),
+
+            0x03 => (Instruction::INC16(WordTarget::BC), 1),
+            0x13 => (Instruction::INC16(WordTarget::DE), 1),
+            0x23 => (Instruction::INC16(WordTarget::HL), 1),
+            0x33 => (Instruction::INC16(WordTarget::SP), 1),
+
+            0x0B => (Instruction::DEC16(WordTarget::BC), 1),
+            0x1B => (Instruction::DEC16(WordTarget::DE), 1),
+            0x2B => (Instruction::DEC16(WordTarget::HL), 1),
+            0x3B => (Instruction::DEC16(WordTarget::SP), 1),
+
+            0xE8 => (Instruction::ADDSPR8(immediate() as i8), 2),
+
+            0x80..=0x87 => (Instruction::ADD(Self::target_from_low_nibble(opcode)), 1),
+            0x88..=0x8F => (Instruction::ADC(Self::target_from_low_nibble(opcode)), 1),
+            0x90..=0x97 => (Instruction::SUB(Self::target_from_low_nibble(opcode)), 1),
+            0x98..=0x9F => (Instruction::SBC(Self::target_from_low_nibble(opcode)), 1),
+            0xA0..=0xA7 => (Instruction::AND(Self::target_from_low_nibble(opcode)), 1),
+            0xA8..=0xAF => (Instruction::XOR(Self::target_from_low_nibble(opcode)), 1),
+            0xB0..=0xB7 => (Instruction::OR(Self::target_from_low_nibble(opcode)), 1),
+            0xB8..=0xBF => (Instruction::CP(Self::target_from_low_nibble(opcode)), 1),
+
+            0xC6 => (Instruction::ADD(AritmeticTarget::D8(immediate())), 2),
+            0xCE => (Instruction::ADC(AritmeticTarget::D8(immediate())), 2),
+            0xD6 => (Instruction::SUB(AritmeticTarget::D8(immediate())), 2),
+            0xDE => (Instruction::SBC(AritmeticTarget::D8(immediate())), 2),
+            0xE6 => (Instruction::AND(AritmeticTarget::D8(immediate())), 2),
+            0xEE => (Instruction::XOR(AritmeticTarget::D8(immediate())), 2),
+            0xF6 => (Instruction::OR(AritmeticTarget::D8(immediate())), 2),
+            0xFE => (Instruction::CP(AritmeticTarget::D8(immediate())), 2),
+
+            0x04 => (Instruction::INC(AritmeticTarget::B), 1),
+            0x0C => (Instruction::INC(AritmeticTarget::C), 1),
+            0x14 => (Instruction::INC(AritmeticTarget::D), 1),
+            0x1C => (Instruction::INC(AritmeticTarget::E), 1),
+            0x24 => (Instruction::INC(AritmeticTarget::H), 1),
+            0x2C => (Instruction::INC(AritmeticTarget::L), 1),
+            0x34 => (Instruction::INC(AritmeticTarget::HL), 1),
+            0x3C => (Instruction::INC(AritmeticTarget::A), 1),
+
+            0x05 => (Instruction::DEC(AritmeticTarget::B), 1),
+            0x0D => (Instruction::DEC(AritmeticTarget::C), 1),
+            0x15 => (Instruction::DEC(AritmeticTarget::D), 1),
+            0x1D => (Instruction::DEC(AritmeticTarget::E), 1),
+            0x25 => (Instruction::DEC(AritmeticTarget::H), 1),
+            0x2D => (Instruction::DEC(AritmeticTarget::L), 1),
+            0x35 => (Instruction::DEC(AritmeticTarget::HL), 1),
+            0x3D => (Instruction::DEC(AritmeticTarget::A), 1),
+
+            0x06 => (Instruction::LD(AritmeticTarget::B, AritmeticTarget::D8(immediate())), 2),
+            0x0E => (Instruction::LD(AritmeticTarget::C, AritmeticTarget::D8(immediate())), 2),
+            0x16 => (Instruction::LD(AritmeticTarget::D, AritmeticTarget::D8(immediate())), 2),
+            0x1E => (Instruction::LD(AritmeticTarget::E, AritmeticTarget::D8(immediate())), 2),
+            0x26 => (Instruction::LD(AritmeticTarget::H, AritmeticTarget::D8(immediate())), 2),
+            0x2E => (Instruction::LD(AritmeticTarget::L, AritmeticTarget::D8(immediate())), 2),
+            0x36 => (Instruction::LD(AritmeticTarget::HL, AritmeticTarget::D8(immediate())), 2),
+            0x3E => (Instruction::LD(AritmeticTarget::A, AritmeticTarget::D8(immediate())), 2),
+
+            _ => panic!("unimplemented opcode: {:#04x}", opcode),
+        }
+    }
+
+    // The CB-prefixed table: eight 8-wide rows of rotate/shift opcodes,
+    // followed by BIT/RES/SET which repeat every 8 opcodes for bit 0..7 of
+    // the same eight targets.
+    fn decode_cb(cb_opcode: u8) -> Instruction {
+        let target = Self::target_from_low3(cb_opcode);
+        match cb_opcode {
+            0x00..=0x07 => Instruction::RLC(target),
+            0x08..=0x0F => Instruction::RRC(target),
+            0x10..=0x17 => Instruction::RL(target),
+            0x18..=0x1F => Instruction::RR(target),
+            0x20..=0x27 => Instruction::SLA(target),
+            0x28..=0x2F => Instruction::SRA(target),
+            0x30..=0x37 => Instruction::SWAP(target),
+            0x38..=0x3F => Instruction::SRL(target),
+            0x40..=0x7F => Instruction::BIT((cb_opcode - 0x40) / 8, target),
+            0x80..=0xBF => Instruction::RES((cb_opcode - 0x80) / 8, target),
+            0xC0..=0xFF => Instruction::SET((cb_opcode - 0xC0) / 8, target),
+        }
+    }
+
+    // Every CB-prefixed row cycles through the same eight targets in the
+    // same order, keyed off the low 3 bits of the opcode.
+    fn target_from_low3(opcode: u8) -> AritmeticTarget {
+        match opcode & 0x07 {
+            0x0 => AritmeticTarget::B,
+            0x1 => AritmeticTarget::C,
+            0x2 => AritmeticTarget::D,
+            0x3 => AritmeticTarget::E,
+            0x4 => AritmeticTarget::H,
+            0x5 => AritmeticTarget::L,
+            0x6 => AritmeticTarget::HL,
+            0x7 => AritmeticTarget::A,
+            _ => unreachable!(),
+        }
+    }
+
+    // The ALU opcode rows (0x80-0xBF) cycle through the same eight targets
+    // in the same order twice per row, keyed off the low nibble of the
+    // opcode.
+    fn target_from_low_nibble(opcode: u8) -> AritmeticTarget {
+        match opcode & 0x0F {
+            0x0 | 0x8 => AritmeticTarget::B,
+            0x1 | 0x9 => AritmeticTarget::C,
+            0x2 | 0xA => AritmeticTarget::D,
+            0x3 | 0xB => AritmeticTarget::E,
+            0x4 | 0xC => AritmeticTarget::H,
+            0x5 | 0xD => AritmeticTarget::L,
+            0x6 | 0xE => AritmeticTarget::HL,
+            0x7 | 0xF => AritmeticTarget::A,
+            _ => unreachable!(),
+        }
+    }
+
+    // Dispatches a decoded instruction and returns the number of T-cycles it
+    // consumed. Every instruction here is unconditional, so the cycle count
+    // only depends on the opcode/operand shape; a future conditional
+    // instruction (JR/JP/CALL/RET with a flag condition) would look up its
+    // taken/not-taken variant from `cycles_for` based on what it observes
+    // while executing, rather than returning a single constant.
+    pub fn execute(&mut self, bus: &mut Bus, instruction: Instruction) -> u8 {
         match instruction {
+            Instruction::NOP => {}
+            Instruction::LD(dst, src) => {
+                let value = self.register_from_target(bus, src);
+                self.store_target(bus, dst, value);
+            }
             Instruction::ADD(target) => {
-                let (new_value, flags) = self.add(target);
+                let (new_value, flags) = self.add(bus, target);
                 self.registers.a = new_value;
                 self.registers.f = flags;
             }
             Instruction::ADC(target) => {
-                let (new_value, flags) = self.add_with_carry(target);
+                let (new_value, flags) = self.add_with_carry(bus, target);
                 self.registers.a = new_value;
                 self.registers.f = flags;
             }
             Instruction::SUB(target) => {
-                let (new_value, flags) = self.subtract(target);
+                let (new_value, flags) = self.subtract(bus, target);
                 self.registers.a = new_value;
                 self.registers.f = flags;
             }
             Instruction::SBC(target) => {
-                let (new_value, flags) = self.subtract_with_carry(target);
+                let (new_value, flags) = self.subtract_with_carry(bus, target);
                 self.registers.a = new_value;
                 self.registers.f = flags;
             }
             Instruction::XOR(target) => {
-                let (new_value, flags) = self.xor(target);
+                let (new_value, flags) = self.xor(bus, target);
                 self.registers.a = new_value;
                 self.registers.f = flags;
             }
             Instruction::AND(target) => {
-                let (new_value, flags) = self.and(target);
+                let (new_value, flags) = self.and(bus, target);
                 self.registers.a = new_value;
                 self.registers.f = flags;
             }
             Instruction::OR(target) => {
-                let (new_value, flags) = self.or(target);
+                let (new_value, flags) = self.or(bus, target);
                 self.registers.a = new_value;
                 self.registers.f = flags;
             }
             Instruction::CP(target) => {
-                let (_, flags) = self.subtract(target); // subtract without updating
+                let (_, flags) = self.subtract(bus, target); // subtract without updating
                 self.registers.f = flags;
             }
             Instruction::INC(target) => {
-                let (new_value, flags) = self.increment(target);
-                *self.register_ref_from_target(target) = new_value;
+                let (new_value, flags) = self.increment(bus, target);
+                self.store_target(bus, target, new_value);
                 self.registers.f = flags
             }
             Instruction::DEC(target) => {
-                let (new_value, flags) = self.decrement(target);
-                *self.register_ref_from_target(target) = new_value;
+                let (new_value, flags) = self.decrement(bus, target);
+                self.store_target(bus, target, new_value);
                 self.registers.f = flags;
             }
             Instruction::SWAP(target) => {
-                let (new_value, flags) = self.swap(target);
-                *self.register_ref_from_target(target) = new_value;
+                let (new_value, flags) = self.swap(bus, target);
+                self.store_target(bus, target, new_value);
+                self.registers.f = flags;
+            }
+            Instruction::RLC(target) => {
+                let (new_value, flags) = self.rotate_left_circular(bus, target);
+                self.store_target(bus, target, new_value);
+                self.registers.f = flags;
+            }
+            Instruction::RRC(target) => {
+                let (new_value, flags) = self.rotate_right_circular(bus, target);
+                self.store_target(bus, target, new_value);
+                self.registers.f = flags;
+            }
+            Instruction::RL(target) => {
+                let (new_value, flags) = self.rotate_left_through_carry(bus, target);
+                self.store_target(bus, target, new_value);
+                self.registers.f = flags;
+            }
+            Instruction::RR(target) => {
+                let (new_value, flags) = self.rotate_right_through_carry(bus, target);
+                self.store_target(bus, target, new_value);
+                self.registers.f = flags;
+            }
+            Instruction::SLA(target) => {
+                let (new_value, flags) = self.shift_left_arithmetic(bus, target);
+                self.store_target(bus, target, new_value);
+                self.registers.f = flags;
+            }
+            Instruction::SRA(target) => {
+                let (new_value, flags) = self.shift_right_arithmetic(bus, target);
+                self.store_target(bus, target, new_value);
+                self.registers.f = flags;
+            }
+            Instruction::SRL(target) => {
+                let (new_value, flags) = self.shift_right_logical(bus, target);
+                self.store_target(bus, target, new_value);
+                self.registers.f = flags;
+            }
+            Instruction::BIT(bit, target) => {
+                self.registers.f = self.test_bit(bus, target, bit);
+            }
+            Instruction::SET(bit, target) => {
+                let value = self.register_from_target(bus, target);
+                self.store_target(bus, target, value | (1 << bit));
+            }
+            Instruction::RES(bit, target) => {
+                let value = self.register_from_target(bus, target);
+                self.store_target(bus, target, value & !(1 << bit));
+            }
+            Instruction::RLCA => {
+                let (new_value, mut flags) = self.rotate_left_circular(bus, AritmeticTarget::A);
+                flags.zero = false;
+                self.registers.a = new_value;
                 self.registers.f = flags;
             }
-            _ => {
-                unimplemented!()
+            Instruction::RRCA => {
+                let (new_value, mut flags) = self.rotate_right_circular(bus, AritmeticTarget::A);
+                flags.zero = false;
+                self.registers.a = new_value;
+                self.registers.f = flags;
+            }
+            Instruction::RLA => {
+                let (new_value, mut flags) = self.rotate_left_through_carry(bus, AritmeticTarget::A);
+                flags.zero = false;
+                self.registers.a = new_value;
+                self.registers.f = flags;
+            }
+            Instruction::RRA => {
+                let (new_value, mut flags) = self.rotate_right_through_carry(bus, AritmeticTarget::A);
+                flags.zero = false;
+                self.registers.a = new_value;
+                self.registers.f = flags;
+            }
+            Instruction::DAA => {
+                let (new_value, flags) = self.decimal_adjust();
+                self.registers.a = new_value;
+                self.registers.f = flags;
+            }
+            Instruction::EI => {
+                self.ei_delay = 2;
+            }
+            Instruction::DI => {
+                self.ime = false;
+                self.ei_delay = 0;
+            }
+            Instruction::HALT => {
+                self.halted = true;
+            }
+            Instruction::RETI => {
+                self.pc = self.pop_word(bus);
+                self.ime = true;
+                self.ei_delay = 0;
+            }
+            Instruction::ADD16(target) => {
+                let (new_value, flags) = self.add16(target);
+                self.registers.set_hl(new_value);
+                self.registers.f = flags;
+            }
+            Instruction::INC16(target) => {
+                let value = self.word_from_target(target).wrapping_add(1);
+                self.set_word(target, value);
+            }
+            Instruction::DEC16(target) => {
+                let value = self.word_from_target(target).wrapping_sub(1);
+                self.set_word(target, value);
+            }
+            Instruction::ADDSPR8(offset) => {
+                let (new_value, flags) = self.add_sp_r8(offset);
+                self.sp = new_value;
+                self.registers.f = flags;
             }
         }
+        Self::cycles_for(instruction)
     }
 
-    pub fn add(&self, target: AritmeticTarget) -> (u8, FlagRegister) {
-        let value = self.register_from_target(target);
+    // The per-opcode T-cycle timing table. A plain register operation takes
+    // 4 T-cycles; a CB prefix byte, an immediate operand byte and each read
+    // or write of `(HL)` add another 4 (so INC (HL) takes 12 and SWAP (HL)
+    // 16). The 16-bit arithmetic ops and RETI have fixed costs of their own.
+    fn cycles_for(instruction: Instruction) -> u8 {
+        match instruction {
+            Instruction::NOP => 4,
+            Instruction::ADD(target)
+            | Instruction::ADC(target)
+            | Instruction::SUB(target)
+            | Instruction::SBC(target)
+            | Instruction::XOR(target)
+            | Instruction::AND(target)
+            | Instruction::OR(target)
+            | Instruction::CP(target) => Self::alu_cycles(target),
+            Instruction::INC(AritmeticTarget::HL) | Instruction::DEC(AritmeticTarget::HL) => 12,
+            Instruction::INC(_) | Instruction::DEC(_) => 4,
+            Instruction::SWAP(AritmeticTarget::HL) => 16,
+            Instruction::SWAP(_) => 8,
+            Instruction::LD(AritmeticTarget::HL, AritmeticTarget::D8(_)) => 12,
+            Instruction::LD(_, AritmeticTarget::D8(_)) => 8,
+            Instruction::LD(AritmeticTarget::HL, _) | Instruction::LD(_, AritmeticTarget::HL) => 8,
+            Instruction::LD(_, _) => 4,
+            Instruction::RLC(target)
+            | Instruction::RRC(target)
+            | Instruction::RL(target)
+            | Instruction::RR(target)
+            | Instruction::SLA(target)
+            | Instruction::SRA(target)
+            | Instruction::SRL(target) => {
+                if target == AritmeticTarget::HL {
+                    16
+                } else {
+                    8
+                }
+            }
+            Instruction::BIT(_, AritmeticTarget::HL) => 12,
+            Instruction::BIT(_, _) => 8,
+            Instruction::SET(_, AritmeticTarget::HL) | Instruction::RES(_, AritmeticTarget::HL) => 16,
+            Instruction::SET(_, _) | Instruction::RES(_, _) => 8,
+            Instruction::RLCA | Instruction::RRCA | Instruction::RLA | Instruction::RRA => 4,
+            Instruction::DAA => 4,
+            Instruction::EI | Instruction::DI | Instruction::HALT => 4,
+            Instruction::RETI => 16,
+            Instruction::ADD16(_) | Instruction::INC16(_) | Instruction::DEC16(_) => 8,
+            Instruction::ADDSPR8(_) => 16,
+        }
+    }
+
+    fn alu_cycles(target: AritmeticTarget) -> u8 {
+        match target {
+            AritmeticTarget::HL | AritmeticTarget::D8(_) => 8,
+            _ => 4,
+        }
+    }
+
+    pub fn add(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
         let (new_value, did_overflow) = self.registers.a.overflowing_add(value);
         let flags = FlagRegister {
             zero: new_value == 0,
@@ -82,21 +519,22 @@ impl CPU {
         (new_value, flags)
     }
 
-    pub fn add_with_carry(&self, target: AritmeticTarget) -> (u8, FlagRegister) {
-        let value = self.register_from_target(target);
-        let big_value = self.registers.a as u16 + value as u16 + (self.registers.f.carry as u16);
+    pub fn add_with_carry(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let carry_in = self.registers.f.carry as u16;
+        let big_value = self.registers.a as u16 + value as u16 + carry_in;
         let new_value = big_value as u8;
         let flags = FlagRegister {
             zero: new_value == 0,
             subtract: false,
-            half_carry: (self.registers.a & 0xF) + (value & 0xF) > 0xF,
+            half_carry: (self.registers.a & 0xF) + (value & 0xF) + carry_in as u8 > 0xF,
             carry: big_value > 0xFF,
         };
         (new_value, flags)
     }
 
-    fn subtract(&self, target: AritmeticTarget) -> (u8, FlagRegister) {
-        let value = self.register_from_target(target);
+    fn subtract(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
         let (new_value, did_overflow) = self.registers.a.overflowing_sub(value);
         let flags = FlagRegister {
             zero: new_value == 0,
@@ -107,23 +545,104 @@ impl CPU {
         (new_value, flags)
     }
 
-    fn subtract_with_carry(&self, target: AritmeticTarget) -> (u8, FlagRegister) {
-        let value = self.register_from_target(target);
-        let (new_value, did_overflow) = self
-            .registers
-            .a
-            .overflowing_sub(value - (self.registers.f.carry as u8));
+    fn subtract_with_carry(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let carry_in = self.registers.f.carry as i16;
+        let result = self.registers.a as i16 - value as i16 - carry_in;
+        let new_value = result as u8;
         let flags = FlagRegister {
             zero: new_value == 0,
             subtract: true,
-            half_carry: ((self.registers.a & 0xF) as i32 - (value & 0xF) as i32) < 0x0, //  https://www.reddit.com/r/EmuDev/comments/4ycoix/a_guide_to_the_gameboys_halfcarry_flag/?utm_source=BD&utm_medium=Search&utm_name=Bing&utm_content=PSR1
-            carry: did_overflow,
+            half_carry: (self.registers.a & 0xF) as i16 - (value & 0xF) as i16 - carry_in < 0, //  https://www.reddit.com/r/EmuDev/comments/4ycoix/a_guide_to_the_gameboys_halfcarry_flag/?utm_source=BD&utm_medium=Search&utm_name=Bing&utm_content=PSR1
+            carry: result < 0,
+        };
+        (new_value, flags)
+    }
+
+    // BCD-corrects `a` after an ADD/ADC or SUB/SBC so that it holds the sum
+    // or difference of two packed-BCD operands. Follows the subtract flag
+    // left behind by the previous instruction to pick the correction
+    // direction; carry is only ever set here, never cleared, since a
+    // correction can only push the result further past 0x99/0xFF.
+    fn decimal_adjust(&self) -> (u8, FlagRegister) {
+        let a = self.registers.a;
+        let mut correction: u8 = 0;
+        let mut carry = self.registers.f.carry;
+
+        if self.registers.f.half_carry || (!self.registers.f.subtract && (a & 0x0F) > 9) {
+            correction |= 0x06;
+        }
+        if self.registers.f.carry || (!self.registers.f.subtract && a > 0x99) {
+            correction |= 0x60;
+            carry = true;
+        }
+
+        let new_value = if self.registers.f.subtract {
+            a.wrapping_sub(correction)
+        } else {
+            a.wrapping_add(correction)
+        };
+
+        let flags = FlagRegister {
+            zero: new_value == 0,
+            subtract: self.registers.f.subtract,
+            half_carry: false,
+            carry,
+        };
+        (new_value, flags)
+    }
+
+    fn word_from_target(&self, target: WordTarget) -> u16 {
+        match target {
+            WordTarget::BC => self.registers.get_bc(),
+            WordTarget::DE => self.registers.get_de(),
+            WordTarget::HL => self.registers.get_hl(),
+            WordTarget::SP => self.sp,
+        }
+    }
+
+    fn set_word(&mut self, target: WordTarget, value: u16) {
+        match target {
+            WordTarget::BC => self.registers.set_bc(value),
+            WordTarget::DE => self.registers.set_de(value),
+            WordTarget::HL => self.registers.set_hl(value),
+            WordTarget::SP => self.sp = value,
+        }
+    }
+
+    // `ADD HL,rr`: unlike the 8-bit ALU ops the zero flag is left untouched;
+    // half_carry/carry come from bit 11/15 overflow of the 16-bit addition.
+    fn add16(&self, target: WordTarget) -> (u16, FlagRegister) {
+        let hl = self.registers.get_hl();
+        let value = self.word_from_target(target);
+        let new_value = hl.wrapping_add(value);
+        let flags = FlagRegister {
+            zero: self.registers.f.zero,
+            subtract: false,
+            half_carry: (hl & 0x0FFF) + (value & 0x0FFF) > 0x0FFF,
+            carry: (hl as u32 + value as u32) > 0xFFFF,
+        };
+        (new_value, flags)
+    }
+
+    // `ADD SP,r8`: the offset is sign-extended for the actual addition, but
+    // half_carry/carry are computed from the low byte only, as if adding the
+    // raw (unsigned) operand byte to the low byte of `sp`.
+    fn add_sp_r8(&self, offset: i8) -> (u16, FlagRegister) {
+        let sp = self.sp;
+        let byte = offset as u8;
+        let new_value = sp.wrapping_add(offset as i16 as u16);
+        let flags = FlagRegister {
+            zero: false,
+            subtract: false,
+            half_carry: (sp & 0x0F) + (byte & 0x0F) as u16 > 0x0F,
+            carry: (sp & 0xFF) + byte as u16 > 0xFF,
         };
         (new_value, flags)
     }
 
-    fn xor(&self, target: AritmeticTarget) -> (u8, FlagRegister) {
-        let value = self.register_from_target(target);
+    fn xor(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
         let new_value = self.registers.a ^ value;
         let flags = FlagRegister {
             zero: new_value == 0,
@@ -134,8 +653,8 @@ impl CPU {
         (new_value, flags)
     }
 
-    fn and(&self, target: AritmeticTarget) -> (u8, FlagRegister) {
-        let value = self.register_from_target(target);
+    fn and(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
         let new_value = self.registers.a & value;
         let flags = FlagRegister {
             zero: new_value == 0,
@@ -146,8 +665,8 @@ impl CPU {
         (new_value, flags)
     }
 
-    fn or(&self, target: AritmeticTarget) -> (u8, FlagRegister) {
-        let value = self.register_from_target(target);
+    fn or(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
         let new_value = self.registers.a | value;
         let flags = FlagRegister {
             zero: new_value == 0,
@@ -158,33 +677,35 @@ impl CPU {
         (new_value, flags)
     }
 
-    fn increment(&self, target: AritmeticTarget) -> (u8, FlagRegister) {
-        let new_value = self.register_from_target(target) + 1;
+    fn increment(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let new_value = value.wrapping_add(1);
         (
             new_value,
             FlagRegister {
                 zero: new_value == 0,
                 subtract: false,
-                half_carry: (self.registers.a & 0xF) + 1 > 0xF,
+                half_carry: (value & 0xF) + 1 > 0xF,
                 carry: self.registers.f.carry,
             },
         )
     }
 
-    fn decrement(&self, target: AritmeticTarget) -> (u8, FlagRegister) {
-        let new_value = self.register_from_target(target) - 1;
+    fn decrement(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let new_value = value.wrapping_sub(1);
         (
             new_value,
             FlagRegister {
                 zero: new_value == 0,
                 subtract: true,
-                half_carry: ((self.registers.a & 0xF) as i32 - 1) < 0x0,
+                half_carry: ((value & 0xF) as i32 - 1) < 0x0,
                 carry: self.registers.f.carry,
             },
         )
     }
-    fn swap(&self, target: AritmeticTarget) -> (u8, FlagRegister) {
-        let value = self.register_from_target(target);
+    fn swap(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
         if value == 0 {
             return (
                 0,
@@ -196,12 +717,12 @@ impl CPU {
                 },
             );
         }
-        let down_nibble = (value & 0x0F) as u8;
-        let upper_nibble = (value & 0xF0) as u8;
-        let new_down_nibble = (upper_nibble >> 4) as u8;
-        let new_upper_nibble = (down_nibble << 4) as u8;
+        let down_nibble = value & 0x0F;
+        let upper_nibble = value & 0xF0;
+        let new_down_nibble = upper_nibble >> 4;
+        let new_upper_nibble = down_nibble << 4;
         let new_value = new_upper_nibble | new_down_nibble;
-        return (
+        (
             new_value,
             FlagRegister {
                 zero: false,
@@ -209,10 +730,104 @@ impl CPU {
                 half_carry: false,
                 carry: false,
             },
-        );
+        )
+    }
+
+    // Circular rotate: the bit that falls off becomes both the new carry
+    // and wraps around into the opposite end of the result.
+    fn rotate_left_circular(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let carry = (value & 0x80) != 0;
+        let new_value = (value << 1) | (carry as u8);
+        (new_value, Self::rotate_flags(new_value, carry))
+    }
+
+    fn rotate_right_circular(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let carry = (value & 0x01) != 0;
+        let new_value = (value >> 1) | ((carry as u8) << 7);
+        (new_value, Self::rotate_flags(new_value, carry))
+    }
+
+    // 9-bit rotate through carry: the old carry flag feeds in on one end and
+    // the bit that falls off becomes the new carry, the carry register
+    // itself acting as the 9th bit.
+    fn rotate_left_through_carry(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let old_carry = self.registers.f.carry;
+        let new_carry = (value & 0x80) != 0;
+        let new_value = (value << 1) | (old_carry as u8);
+        (new_value, Self::rotate_flags(new_value, new_carry))
+    }
+
+    fn rotate_right_through_carry(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let old_carry = self.registers.f.carry;
+        let new_carry = (value & 0x01) != 0;
+        let new_value = (value >> 1) | ((old_carry as u8) << 7);
+        (new_value, Self::rotate_flags(new_value, new_carry))
     }
 
-    fn register_from_target(&self, target: AritmeticTarget) -> u8 {
+    fn shift_left_arithmetic(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let carry = (value & 0x80) != 0;
+        let new_value = value << 1;
+        (new_value, Self::rotate_flags(new_value, carry))
+    }
+
+    fn shift_right_logical(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let carry = (value & 0x01) != 0;
+        let new_value = value >> 1;
+        (new_value, Self::rotate_flags(new_value, carry))
+    }
+
+    // Arithmetic shift: bit7 is preserved rather than shifted in as 0, so
+    // the sign of a two's-complement value is kept.
+    fn shift_right_arithmetic(&self, bus: &Bus, target: AritmeticTarget) -> (u8, FlagRegister) {
+        let value = self.register_from_target(bus, target);
+        let carry = (value & 0x01) != 0;
+        let new_value = (value >> 1) | (value & 0x80);
+        (new_value, Self::rotate_flags(new_value, carry))
+    }
+
+    fn rotate_flags(new_value: u8, carry: bool) -> FlagRegister {
+        FlagRegister {
+            zero: new_value == 0,
+            subtract: false,
+            half_carry: false,
+            carry,
+        }
+    }
+
+    fn test_bit(&self, bus: &Bus, target: AritmeticTarget, bit: u8) -> FlagRegister {
+        let value = self.register_from_target(bus, target);
+        FlagRegister {
+            zero: (value >> bit) & 1 == 0,
+            subtract: false,
+            half_carry: true,
+            carry: self.registers.f.carry,
+        }
+    }
+
+    // The stack grows downward: `sp` is decremented before each byte is
+    // written, high byte first, so `pop_word` can read it back low-then-high.
+    fn push_word(&mut self, bus: &mut Bus, value: u16) {
+        self.sp = self.sp.wrapping_sub(1);
+        bus.write(self.sp, (value >> 8) as u8);
+        self.sp = self.sp.wrapping_sub(1);
+        bus.write(self.sp, (value & 0xFF) as u8);
+    }
+
+    fn pop_word(&mut self, bus: &Bus) -> u16 {
+        let low = bus.read(self.sp) as u16;
+        self.sp = self.sp.wrapping_add(1);
+        let high = bus.read(self.sp) as u16;
+        self.sp = self.sp.wrapping_add(1);
+        (high << 8) | low
+    }
+
+    fn register_from_target(&self, bus: &Bus, target: AritmeticTarget) -> u8 {
         match target {
             AritmeticTarget::A => self.registers.a,
             AritmeticTarget::B => self.registers.b,
@@ -221,18 +836,132 @@ impl CPU {
             AritmeticTarget::E => self.registers.e,
             AritmeticTarget::H => self.registers.h,
             AritmeticTarget::L => self.registers.l,
+            AritmeticTarget::HL => bus.read(self.registers.get_hl()),
+            AritmeticTarget::D8(value) => value,
         }
     }
 
-    fn register_ref_from_target(&mut self, target: AritmeticTarget) -> &mut u8 {
+    fn store_target(&mut self, bus: &mut Bus, target: AritmeticTarget, value: u8) {
         match target {
-            AritmeticTarget::A => &mut self.registers.a,
-            AritmeticTarget::B => &mut self.registers.b,
-            AritmeticTarget::C => &mut self.registers.c,
-            AritmeticTarget::D => &mut self.registers.d,
-            AritmeticTarget::E => &mut self.registers.e,
-            AritmeticTarget::H => &mut self.registers.h,
-            AritmeticTarget::L => &mut self.registers.l,
+            AritmeticTarget::A => self.registers.a = value,
+            AritmeticTarget::B => self.registers.b = value,
+            AritmeticTarget::C => self.registers.c = value,
+            AritmeticTarget::D => self.registers.d = value,
+            AritmeticTarget::E => self.registers.e = value,
+            AritmeticTarget::H => self.registers.h = value,
+            AritmeticTarget::L => self.registers.l = value,
+            AritmeticTarget::HL => bus.write(self.registers.get_hl(), value),
+            AritmeticTarget::D8(_) => unreachable!("D8 is an immediate source, never a store target"),
+        }
+    }
+
+    // Decodes a handful of instructions starting at `pc` without executing
+    // them, for display in a debugger.
+    fn disassemble(&self, bus: &Bus, count: u8) -> String {
+        let mut addr = self.pc;
+        let mut lines = Vec::new();
+        for _ in 0..count {
+            let (instruction, length) = self.decode_at(bus, addr);
+            lines.push(format!("{:#06x}: {:?}", addr, instruction));
+            addr = addr.wrapping_add(length);
+        }
+        lines.join("\n")
+    }
+}
+
+impl Debuggable for CPU {
+    fn add_breakpoint(&mut self, addr: u16) {
+        if !self.breakpoints.contains(&addr) {
+            self.breakpoints.push(addr);
+        }
+    }
+
+    fn remove_breakpoint(&mut self, addr: u16) {
+        self.breakpoints.retain(|&bp| bp != addr);
+    }
+
+    fn add_watchpoint(&mut self, addr: u16) {
+        if !self.watchpoints.contains(&addr) {
+            self.watchpoints.push(addr);
+        }
+    }
+
+    fn remove_watchpoint(&mut self, addr: u16) {
+        self.watchpoints.retain(|&wp| wp != addr);
+    }
+
+    fn step_debug(&mut self, bus: &mut Bus) -> StepEvent {
+        if self.breakpoints.contains(&self.pc) {
+            return StepEvent::Breakpoint(self.pc);
+        }
+
+        let watched_before: Vec<u8> = self.watchpoints.iter().map(|&addr| bus.read(addr)).collect();
+        let cycles = self.step(bus);
+
+        for (&addr, &before) in self.watchpoints.iter().zip(watched_before.iter()) {
+            if bus.read(addr) != before {
+                return StepEvent::Watchpoint(addr);
+            }
+        }
+
+        StepEvent::Stepped(cycles)
+    }
+
+    fn dump_state(&self, bus: &Bus) -> String {
+        format!(
+            "PC:{:#06x} SP:{:#06x} cycles:{} IME:{} HALT:{}\n\
+             A:{:#04x} F:{:#04x} (Z:{} N:{} H:{} C:{}) B:{:#04x} C:{:#04x} D:{:#04x} E:{:#04x} H:{:#04x} L:{:#04x}\n\
+             AF:{:#06x} BC:{:#06x} DE:{:#06x} HL:{:#06x}\n\
+             {}",
+            self.pc,
+            self.sp,
+            self.cycles,
+            self.ime,
+            self.halted,
+            self.registers.a,
+            u8::from(self.registers.f),
+            self.registers.f.zero,
+            self.registers.f.subtract,
+            self.registers.f.half_carry,
+            self.registers.f.carry,
+            self.registers.b,
+            self.registers.c,
+            self.registers.d,
+            self.registers.e,
+            self.registers.h,
+            self.registers.l,
+            self.registers.get_af(),
+            self.registers.get_bc(),
+            self.registers.get_de(),
+            self.registers.get_hl(),
+            self.disassemble(bus, 3),
+        )
+    }
+
+    fn execute_command(&mut self, bus: &mut Bus, command: &[&str]) -> String {
+        match command {
+            ["break", addr] | ["b", addr] => match u16::from_str_radix(addr, 16) {
+                Ok(addr) => {
+                    self.add_breakpoint(addr);
+                    format!("breakpoint set at {:#06x}", addr)
+                }
+                Err(_) => format!("not a hex address: {}", addr),
+            },
+            ["watch", addr] | ["w", addr] => match u16::from_str_radix(addr, 16) {
+                Ok(addr) => {
+                    self.add_watchpoint(addr);
+                    format!("watchpoint set at {:#06x}", addr)
+                }
+                Err(_) => format!("not a hex address: {}", addr),
+            },
+            ["step"] | ["s"] => match self.step_debug(bus) {
+                StepEvent::Breakpoint(addr) => format!("hit breakpoint at {:#06x}", addr),
+                StepEvent::Watchpoint(addr) => format!("hit watchpoint at {:#06x}", addr),
+                StepEvent::Stepped(cycles) => format!("stepped, {} cycles", cycles),
+            },
+            ["regs"] | ["dump"] => self.dump_state(bus),
+            ["disasm"] => self.disassemble(bus, 5),
+            _ => "usage: break <addr>|watch <addr>|step|regs|disasm".to_string(),
         }
     }
 }
@@ -240,12 +969,12 @@ impl CPU {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use memory::Bus;
 
     #[test]
     fn given_cpu_when_adding_update_a_with_the_sum_of_a_and_the_target_register() {
-        let mut cpu = CPU {
-            registers: Registers::new(),
-        };
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
         cpu.registers.a = 10;
         cpu.registers.b = 1;
         cpu.registers.c = 2;
@@ -254,99 +983,725 @@ mod tests {
         cpu.registers.h = 5;
         cpu.registers.l = 6;
 
-        cpu.execute(Instruction::ADD(AritmeticTarget::B));
+        cpu.execute(&mut bus, Instruction::ADD(AritmeticTarget::B));
         assert_eq!(cpu.registers.a, 11);
 
         cpu.registers.a = 10;
-        cpu.execute(Instruction::ADD(AritmeticTarget::C));
+        cpu.execute(&mut bus, Instruction::ADD(AritmeticTarget::C));
         assert_eq!(cpu.registers.a, 12);
 
         cpu.registers.a = 10;
-        cpu.execute(Instruction::ADD(AritmeticTarget::D));
+        cpu.execute(&mut bus, Instruction::ADD(AritmeticTarget::D));
         assert_eq!(cpu.registers.a, 13);
 
         cpu.registers.a = 10;
-        cpu.execute(Instruction::ADD(AritmeticTarget::E));
+        cpu.execute(&mut bus, Instruction::ADD(AritmeticTarget::E));
         assert_eq!(cpu.registers.a, 14);
 
         cpu.registers.a = 10;
-        cpu.execute(Instruction::ADD(AritmeticTarget::H));
+        cpu.execute(&mut bus, Instruction::ADD(AritmeticTarget::H));
         assert_eq!(cpu.registers.a, 15);
 
         cpu.registers.a = 10;
-        cpu.execute(Instruction::ADD(AritmeticTarget::L));
+        cpu.execute(&mut bus, Instruction::ADD(AritmeticTarget::L));
         assert_eq!(cpu.registers.a, 16);
     }
 
     #[test]
     fn given_cpu_when_incrementing_a_register_then_register_should_be_incremented_by_one() {
-        let mut cpu = CPU {
-            registers: Registers::new(),
-        };
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
 
         cpu.registers.a = 0;
-        cpu.execute(Instruction::INC(AritmeticTarget::A));
+        cpu.execute(&mut bus, Instruction::INC(AritmeticTarget::A));
         assert_eq!(cpu.registers.a, 1);
-        cpu.execute(Instruction::DEC(AritmeticTarget::A));
+        cpu.execute(&mut bus, Instruction::DEC(AritmeticTarget::A));
         assert_eq!(cpu.registers.a, 0);
 
         cpu.registers.b = 0;
-        cpu.execute(Instruction::INC(AritmeticTarget::B));
+        cpu.execute(&mut bus, Instruction::INC(AritmeticTarget::B));
         assert_eq!(cpu.registers.b, 1);
-        cpu.execute(Instruction::DEC(AritmeticTarget::B));
+        cpu.execute(&mut bus, Instruction::DEC(AritmeticTarget::B));
         assert_eq!(cpu.registers.b, 0);
 
         cpu.registers.c = 2;
-        cpu.execute(Instruction::INC(AritmeticTarget::C));
+        cpu.execute(&mut bus, Instruction::INC(AritmeticTarget::C));
         assert_eq!(cpu.registers.c, 3);
-        cpu.execute(Instruction::DEC(AritmeticTarget::C));
+        cpu.execute(&mut bus, Instruction::DEC(AritmeticTarget::C));
         assert_eq!(cpu.registers.c, 2);
 
         cpu.registers.d = 3;
-        cpu.execute(Instruction::INC(AritmeticTarget::D));
+        cpu.execute(&mut bus, Instruction::INC(AritmeticTarget::D));
         assert_eq!(cpu.registers.d, 4);
-        cpu.execute(Instruction::DEC(AritmeticTarget::D));
+        cpu.execute(&mut bus, Instruction::DEC(AritmeticTarget::D));
         assert_eq!(cpu.registers.d, 3);
 
         cpu.registers.e = 4;
-        cpu.execute(Instruction::INC(AritmeticTarget::E));
+        cpu.execute(&mut bus, Instruction::INC(AritmeticTarget::E));
         assert_eq!(cpu.registers.e, 5);
-        cpu.execute(Instruction::DEC(AritmeticTarget::E));
+        cpu.execute(&mut bus, Instruction::DEC(AritmeticTarget::E));
         assert_eq!(cpu.registers.e, 4);
 
         cpu.registers.h = 5;
-        cpu.execute(Instruction::INC(AritmeticTarget::H));
+        cpu.execute(&mut bus, Instruction::INC(AritmeticTarget::H));
         assert_eq!(cpu.registers.h, 6);
-        cpu.execute(Instruction::DEC(AritmeticTarget::H));
+        cpu.execute(&mut bus, Instruction::DEC(AritmeticTarget::H));
         assert_eq!(cpu.registers.h, 5);
 
         cpu.registers.l = 6;
-        cpu.execute(Instruction::INC(AritmeticTarget::L));
+        cpu.execute(&mut bus, Instruction::INC(AritmeticTarget::L));
         assert_eq!(cpu.registers.l, 7);
-        cpu.execute(Instruction::DEC(AritmeticTarget::L));
+        cpu.execute(&mut bus, Instruction::DEC(AritmeticTarget::L));
         assert_eq!(cpu.registers.l, 6);
     }
 
+    #[test]
+    fn given_cpu_when_inc_or_dec_wraps_past_the_u8_boundary_then_it_does_not_panic() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+
+        cpu.registers.a = 0xFF;
+        cpu.execute(&mut bus, Instruction::INC(AritmeticTarget::A));
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.f.zero);
+
+        cpu.registers.b = 0x00;
+        cpu.execute(&mut bus, Instruction::DEC(AritmeticTarget::B));
+        assert_eq!(cpu.registers.b, 0xFF);
+        assert!(!cpu.registers.f.zero);
+    }
+
     #[test]
     fn given_cpu_when_swapping_then_register_should_have_nibble_swapped() {
-        let mut cpu = CPU {
-            registers: Registers::new(),
-        };
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
 
         cpu.registers.b = 0b11110000;
-        cpu.execute(Instruction::SWAP(AritmeticTarget::B));
+        cpu.execute(&mut bus, Instruction::SWAP(AritmeticTarget::B));
         assert_eq!(cpu.registers.b, 0b00001111);
 
         cpu.registers.c = 0b00001111;
-        cpu.execute(Instruction::SWAP(AritmeticTarget::C));
+        cpu.execute(&mut bus, Instruction::SWAP(AritmeticTarget::C));
         assert_eq!(cpu.registers.c, 0b11110000);
 
         cpu.registers.e = 0b01111110;
-        cpu.execute(Instruction::SWAP(AritmeticTarget::E));
+        cpu.execute(&mut bus, Instruction::SWAP(AritmeticTarget::E));
         assert_eq!(cpu.registers.e, 0b11100111);
 
         cpu.registers.e = 0;
-        cpu.execute(Instruction::SWAP(AritmeticTarget::E));
+        cpu.execute(&mut bus, Instruction::SWAP(AritmeticTarget::E));
         assert_eq!(cpu.registers.e, 0);
     }
+
+    #[test]
+    fn given_cpu_when_adding_the_hl_indirect_target_then_the_byte_is_read_from_the_bus() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        bus.write(0xC000, 5);
+        cpu.registers.set_hl(0xC000);
+        cpu.registers.a = 10;
+
+        cpu.execute(&mut bus, Instruction::ADD(AritmeticTarget::HL));
+        assert_eq!(cpu.registers.a, 15);
+
+        cpu.execute(&mut bus, Instruction::INC(AritmeticTarget::HL));
+        assert_eq!(bus.read(0xC000), 6);
+    }
+
+    #[test]
+    fn given_cpu_when_stepping_an_immediate_add_then_a_is_updated_and_pc_advances_past_the_operand()
+    {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0xC6; // ADD A,d8
+        rom[1] = 0x05;
+        let mut bus = Bus::new(rom);
+        cpu.registers.a = 10;
+
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.registers.a, 15);
+        assert_eq!(cpu.pc, 2);
+    }
+
+    #[test]
+    fn given_cpu_when_stepping_a_register_add_then_pc_only_advances_by_one() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x80; // ADD A,B
+        let mut bus = Bus::new(rom);
+        cpu.registers.a = 1;
+        cpu.registers.b = 2;
+
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.registers.a, 3);
+        assert_eq!(cpu.pc, 1);
+    }
+
+    #[test]
+    fn given_cpu_when_stepping_a_cb_prefixed_swap_then_pc_advances_by_two() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0xCB;
+        rom[1] = 0x30; // SWAP B
+        let mut bus = Bus::new(rom);
+        cpu.registers.b = 0b11110000;
+
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.registers.b, 0b00001111);
+        assert_eq!(cpu.pc, 2);
+    }
+
+    #[test]
+    fn given_cpu_when_stepping_an_immediate_load_then_the_target_register_is_set() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x3E; // LD A,d8
+        rom[1] = 0x42;
+        let mut bus = Bus::new(rom);
+
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.registers.a, 0x42);
+        assert_eq!(cpu.pc, 2);
+    }
+
+    #[test]
+    fn given_cpu_when_stepping_a_register_add_then_four_cycles_are_consumed() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x80; // ADD A,B
+
+        let cycles = cpu.step(&mut Bus::new(rom));
+
+        assert_eq!(cycles, 4);
+        assert_eq!(cpu.cycles, 4);
+    }
+
+    #[test]
+    fn given_cpu_when_stepping_an_immediate_add_then_eight_cycles_are_consumed() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0xC6; // ADD A,d8
+        rom[1] = 0x01;
+
+        let cycles = cpu.step(&mut Bus::new(rom));
+
+        assert_eq!(cycles, 8);
+        assert_eq!(cpu.cycles, 8);
+    }
+
+    #[test]
+    fn given_cpu_when_stepping_an_hl_indirect_increment_then_twelve_cycles_are_consumed() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x34; // INC (HL)
+        cpu.registers.set_hl(0xC000);
+
+        let cycles = cpu.step(&mut Bus::new(rom));
+
+        assert_eq!(cycles, 12);
+    }
+
+    #[test]
+    fn given_cpu_when_stepping_multiple_instructions_then_cycles_accumulate() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x00; // NOP
+        rom[1] = 0x80; // ADD A,B
+        let mut bus = Bus::new(rom);
+
+        cpu.step(&mut bus);
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.cycles, 8);
+    }
+
+    #[test]
+    fn given_cpu_when_rlc_then_bit7_becomes_both_the_carry_and_the_new_bit0() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.b = 0b10000001;
+
+        cpu.execute(&mut bus, Instruction::RLC(AritmeticTarget::B));
+
+        assert_eq!(cpu.registers.b, 0b00000011);
+        assert!(cpu.registers.f.carry);
+        assert!(!cpu.registers.f.zero);
+    }
+
+    #[test]
+    fn given_cpu_when_rl_then_the_old_carry_feeds_in_and_bit7_becomes_the_new_carry() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.b = 0b10000001;
+        cpu.registers.f.carry = true;
+
+        cpu.execute(&mut bus, Instruction::RL(AritmeticTarget::B));
+
+        assert_eq!(cpu.registers.b, 0b00000011);
+        assert!(cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_sra_then_bit7_is_preserved_but_carry_comes_from_bit0() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.b = 0b10000001;
+
+        cpu.execute(&mut bus, Instruction::SRA(AritmeticTarget::B));
+
+        assert_eq!(cpu.registers.b, 0b11000000);
+        assert!(cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_srl_then_zero_is_shifted_in_from_the_top() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.b = 0b10000001;
+
+        cpu.execute(&mut bus, Instruction::SRL(AritmeticTarget::B));
+
+        assert_eq!(cpu.registers.b, 0b01000000);
+        assert!(cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_bit_then_zero_flag_reflects_the_tested_bit_and_carry_is_untouched() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.b = 0b00000010;
+        cpu.registers.f.carry = true;
+
+        cpu.execute(&mut bus, Instruction::BIT(1, AritmeticTarget::B));
+        assert!(!cpu.registers.f.zero);
+        assert!(cpu.registers.f.half_carry);
+        assert!(cpu.registers.f.carry);
+
+        cpu.execute(&mut bus, Instruction::BIT(0, AritmeticTarget::B));
+        assert!(cpu.registers.f.zero);
+    }
+
+    #[test]
+    fn given_cpu_when_set_and_res_then_only_the_target_bit_changes_and_flags_are_left_alone() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.b = 0;
+        cpu.registers.f.zero = true;
+
+        cpu.execute(&mut bus, Instruction::SET(3, AritmeticTarget::B));
+        assert_eq!(cpu.registers.b, 0b00001000);
+        assert!(cpu.registers.f.zero);
+
+        cpu.execute(&mut bus, Instruction::RES(3, AritmeticTarget::B));
+        assert_eq!(cpu.registers.b, 0);
+        assert!(cpu.registers.f.zero);
+    }
+
+    #[test]
+    fn given_cpu_when_rlca_then_the_zero_flag_is_always_cleared() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.a = 0;
+
+        cpu.execute(&mut bus, Instruction::RLCA);
+
+        assert_eq!(cpu.registers.a, 0);
+        assert!(!cpu.registers.f.zero);
+        assert!(!cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_stepping_a_cb_prefixed_bit_then_twelve_cycles_are_consumed_for_hl() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0xCB;
+        rom[1] = 0x46; // BIT 0,(HL)
+        cpu.registers.set_hl(0xC000);
+
+        let cycles = cpu.step(&mut Bus::new(rom));
+
+        assert_eq!(cycles, 12);
+    }
+
+    #[test]
+    fn given_cpu_when_adc_with_incoming_carry_then_half_carry_accounts_for_it() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.a = 0x0F;
+        cpu.registers.b = 0x00;
+        cpu.registers.f.carry = true;
+
+        cpu.execute(&mut bus, Instruction::ADC(AritmeticTarget::B));
+
+        assert_eq!(cpu.registers.a, 0x10);
+        assert!(cpu.registers.f.half_carry);
+        assert!(!cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_sbc_with_borrow_that_would_underflow_u8_then_it_does_not_panic() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.a = 0x00;
+        cpu.registers.b = 0xFF;
+        cpu.registers.f.carry = true;
+
+        cpu.execute(&mut bus, Instruction::SBC(AritmeticTarget::B));
+
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.f.carry);
+        assert!(cpu.registers.f.half_carry);
+    }
+
+    #[test]
+    fn given_cpu_when_sbc_then_borrow_is_folded_into_half_carry() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.a = 0x10;
+        cpu.registers.b = 0x00;
+        cpu.registers.f.carry = true;
+
+        cpu.execute(&mut bus, Instruction::SBC(AritmeticTarget::B));
+
+        assert_eq!(cpu.registers.a, 0x0F);
+        assert!(cpu.registers.f.half_carry);
+        assert!(!cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_daa_after_adding_two_bcd_bytes_then_the_result_is_corrected_to_bcd() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.a = 0x45;
+        cpu.registers.b = 0x38;
+
+        cpu.execute(&mut bus, Instruction::ADD(AritmeticTarget::B));
+        assert_eq!(cpu.registers.a, 0x7D);
+
+        cpu.execute(&mut bus, Instruction::DAA);
+
+        assert_eq!(cpu.registers.a, 0x83);
+        assert!(!cpu.registers.f.carry);
+        assert!(!cpu.registers.f.zero);
+    }
+
+    #[test]
+    fn given_cpu_when_daa_after_an_addition_overflows_a_bcd_byte_then_carry_is_set() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.a = 0x90;
+        cpu.registers.b = 0x90;
+
+        cpu.execute(&mut bus, Instruction::ADD(AritmeticTarget::B));
+        cpu.execute(&mut bus, Instruction::DAA);
+
+        assert_eq!(cpu.registers.a, 0x80);
+        assert!(cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_daa_after_subtracting_two_bcd_bytes_then_the_result_is_corrected_to_bcd() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.a = 0x45;
+        cpu.registers.b = 0x38;
+
+        cpu.execute(&mut bus, Instruction::SUB(AritmeticTarget::B));
+        assert_eq!(cpu.registers.a, 0x0D);
+
+        cpu.execute(&mut bus, Instruction::DAA);
+
+        assert_eq!(cpu.registers.a, 0x07);
+        assert!(!cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_di_then_ime_is_cleared_immediately() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.ime = true;
+
+        cpu.execute(&mut bus, Instruction::DI);
+
+        assert!(!cpu.ime);
+    }
+
+    #[test]
+    fn given_cpu_when_ei_then_ime_only_turns_on_after_the_following_instruction() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0xFB; // EI
+        rom[1] = 0x00; // NOP
+        rom[2] = 0x00; // NOP
+        let mut bus = Bus::new(rom);
+
+        cpu.step(&mut bus); // EI
+        assert!(!cpu.ime);
+
+        cpu.step(&mut bus); // the instruction right after EI still runs masked
+        assert!(cpu.ime);
+
+        cpu.step(&mut bus);
+        assert!(cpu.ime);
+    }
+
+    #[test]
+    fn given_cpu_when_halt_then_stepping_idles_until_an_interrupt_is_pending() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x76; // HALT
+        let mut bus = Bus::new(rom);
+
+        cpu.step(&mut bus);
+        assert!(cpu.halted);
+
+        let idle_cycles = cpu.step(&mut bus);
+        assert_eq!(idle_cycles, 4);
+        assert!(cpu.halted);
+        assert_eq!(cpu.pc, 1); // fetching stayed suspended, pc did not advance
+
+        bus.write(0xFFFF, 0b00000001); // IE: VBlank enabled
+        bus.write(0xFF0F, 0b00000001); // IF: VBlank pending
+        cpu.step(&mut bus);
+        assert!(!cpu.halted);
+    }
+
+    #[test]
+    fn given_ime_set_and_a_pending_enabled_interrupt_then_it_is_serviced_before_the_next_instruction(
+    ) {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x00; // NOP, never reached this step
+        let mut bus = Bus::new(rom);
+        cpu.ime = true;
+        cpu.pc = 0x0150;
+        cpu.sp = 0xFFFE;
+        bus.write(0xFFFF, 0b00000001); // IE: VBlank enabled
+        bus.write(0xFF0F, 0b00000001); // IF: VBlank pending
+
+        cpu.step(&mut bus);
+
+        assert_eq!(cpu.pc, 0x40); // jumped to the VBlank vector
+        assert!(!cpu.ime);
+        assert_eq!(bus.read(0xFF0F), 0); // the serviced interrupt's IF bit is cleared
+        assert_eq!(cpu.sp, 0xFFFC);
+        assert_eq!(bus.read(0xFFFD), 0x01);
+        assert_eq!(bus.read(0xFFFC), 0x50);
+    }
+
+    #[test]
+    fn given_cpu_when_reti_then_pc_is_popped_and_ime_is_restored() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.sp = 0xFFFC;
+        bus.write(0xFFFC, 0x50);
+        bus.write(0xFFFD, 0x01);
+
+        cpu.execute(&mut bus, Instruction::RETI);
+
+        assert_eq!(cpu.pc, 0x0150);
+        assert!(cpu.ime);
+        assert_eq!(cpu.sp, 0xFFFE);
+    }
+
+    #[test]
+    fn given_cpu_when_add_hl_rr_then_zero_flag_is_left_unchanged_but_half_carry_and_carry_are_set() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.set_hl(0x0FFF);
+        cpu.registers.set_bc(0x0001);
+        cpu.registers.f.zero = true;
+
+        cpu.execute(&mut bus, Instruction::ADD16(WordTarget::BC));
+
+        assert_eq!(cpu.registers.get_hl(), 0x1000);
+        assert!(cpu.registers.f.zero);
+        assert!(!cpu.registers.f.subtract);
+        assert!(cpu.registers.f.half_carry);
+        assert!(!cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_add_hl_rr_overflows_16_bits_then_carry_is_set_and_hl_wraps() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.set_hl(0xFFFF);
+        cpu.registers.set_bc(0x0001);
+
+        cpu.execute(&mut bus, Instruction::ADD16(WordTarget::BC));
+
+        assert_eq!(cpu.registers.get_hl(), 0x0000);
+        assert!(cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_inc16_or_dec16_then_no_flags_are_affected() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.registers.set_bc(0xFFFF);
+        cpu.registers.f = FlagRegister {
+            zero: true,
+            subtract: true,
+            half_carry: true,
+            carry: true,
+        };
+
+        cpu.execute(&mut bus, Instruction::INC16(WordTarget::BC));
+        assert_eq!(cpu.registers.get_bc(), 0x0000);
+        assert_eq!(cpu.registers.f, FlagRegister {
+            zero: true,
+            subtract: true,
+            half_carry: true,
+            carry: true,
+        });
+
+        cpu.execute(&mut bus, Instruction::DEC16(WordTarget::BC));
+        assert_eq!(cpu.registers.get_bc(), 0xFFFF);
+        assert_eq!(cpu.registers.f, FlagRegister {
+            zero: true,
+            subtract: true,
+            half_carry: true,
+            carry: true,
+        });
+    }
+
+    #[test]
+    fn given_cpu_when_add_sp_r8_with_a_positive_offset_then_zero_and_subtract_are_always_cleared() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.sp = 0x0005;
+        cpu.registers.f.zero = true;
+
+        cpu.execute(&mut bus, Instruction::ADDSPR8(3));
+
+        assert_eq!(cpu.sp, 0x0008);
+        assert!(!cpu.registers.f.zero);
+        assert!(!cpu.registers.f.subtract);
+        assert!(!cpu.registers.f.half_carry);
+        assert!(!cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_cpu_when_add_sp_r8_with_a_negative_offset_then_half_carry_and_carry_come_from_the_low_byte(
+    ) {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+        cpu.sp = 0x0001;
+
+        cpu.execute(&mut bus, Instruction::ADDSPR8(-1));
+
+        assert_eq!(cpu.sp, 0x0000);
+        assert!(cpu.registers.f.half_carry);
+        assert!(cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn given_a_breakpoint_at_pc_then_step_debug_stops_before_executing() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x3C; // INC A
+        let mut bus = Bus::new(rom);
+        cpu.add_breakpoint(0x0000);
+
+        let event = cpu.step_debug(&mut bus);
+
+        assert_eq!(event, StepEvent::Breakpoint(0x0000));
+        assert_eq!(cpu.registers.a, 0);
+        assert_eq!(cpu.pc, 0x0000);
+    }
+
+    #[test]
+    fn given_a_watchpoint_then_step_debug_reports_it_once_the_watched_byte_changes() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x36; // LD (HL),d8
+        rom[1] = 0x42;
+        cpu.registers.set_hl(0xC000);
+        let mut bus = Bus::new(rom);
+        cpu.add_watchpoint(0xC000);
+
+        let event = cpu.step_debug(&mut bus);
+
+        assert_eq!(event, StepEvent::Watchpoint(0xC000));
+        assert_eq!(bus.read(0xC000), 0x42);
+    }
+
+    #[test]
+    fn given_no_breakpoint_or_watchpoint_then_step_debug_runs_normally() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x3C; // INC A
+        let mut bus = Bus::new(rom);
+
+        let event = cpu.step_debug(&mut bus);
+
+        assert_eq!(event, StepEvent::Stepped(4));
+        assert_eq!(cpu.registers.a, 1);
+    }
+
+    #[test]
+    fn given_a_removed_breakpoint_then_step_debug_no_longer_stops_there() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x3C; // INC A
+        let mut bus = Bus::new(rom);
+        cpu.add_breakpoint(0x0000);
+        cpu.remove_breakpoint(0x0000);
+
+        let event = cpu.step_debug(&mut bus);
+
+        assert_eq!(event, StepEvent::Stepped(4));
+    }
+
+    #[test]
+    fn given_cpu_when_dumping_state_then_registers_flags_and_a_disassembly_are_included() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x00; // NOP
+        let bus = Bus::new(rom);
+        cpu.registers.a = 0x42;
+        cpu.registers.f.zero = true;
+
+        let dump = cpu.dump_state(&bus);
+
+        assert!(dump.contains("A:0x42"));
+        assert!(dump.contains("Z:true"));
+        assert!(dump.contains("0x0000: NOP"));
+    }
+
+    #[test]
+    fn given_the_break_command_then_a_breakpoint_is_set_and_reported() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+
+        let output = cpu.execute_command(&mut bus, &["break", "0150"]);
+
+        assert_eq!(output, "breakpoint set at 0x0150");
+    }
+
+    #[test]
+    fn given_the_step_command_then_it_delegates_to_step_debug() {
+        let mut cpu = CPU::new();
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0x00; // NOP
+        let mut bus = Bus::new(rom);
+
+        let output = cpu.execute_command(&mut bus, &["step"]);
+
+        assert_eq!(output, "stepped, 4 cycles");
+    }
+
+    #[test]
+    fn given_an_unrecognized_command_then_a_usage_message_is_returned() {
+        let mut cpu = CPU::new();
+        let mut bus = Bus::new(vec![0; 0x8000]);
+
+        let output = cpu.execute_command(&mut bus, &["frobnicate"]);
+
+        assert_eq!(output, "usage: break <addr>|watch <addr>|step|regs|disasm");
+    }
 }
@@ -0,0 +1,37 @@
+use memory::Bus;
+
+// What a single debug-mode step produced: either the instruction ran to
+// completion, or a breakpoint/watchpoint stopped the CPU before (or right
+// after) it could.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepEvent {
+    // `pc` matched a breakpoint; the instruction at it was not executed.
+    Breakpoint(u16),
+    // The byte at this watched address changed as a result of the step.
+    Watchpoint(u16),
+    // The instruction ran normally, consuming this many T-cycles.
+    Stepped(u8),
+}
+
+// A stepping debugger layered over a CPU: breakpoints on `pc`, watchpoints
+// on memory addresses, single-stepping, state dumps and a tiny command
+// dispatcher for a front-end REPL.
+pub trait Debuggable {
+    fn add_breakpoint(&mut self, addr: u16);
+    fn remove_breakpoint(&mut self, addr: u16);
+    fn add_watchpoint(&mut self, addr: u16);
+    fn remove_watchpoint(&mut self, addr: u16);
+
+    // Checks breakpoints before running the next instruction; runs it and
+    // checks watchpoints only if no breakpoint fired.
+    fn step_debug(&mut self, bus: &mut Bus) -> StepEvent;
+
+    // The full register file (including decoded flag bits) plus a short
+    // disassembly of the next few instructions at `pc`.
+    fn dump_state(&self, bus: &Bus) -> String;
+
+    // Dispatches a REPL command line, already split on whitespace, and
+    // returns the text a front-end should print. Unrecognized commands
+    // return a short usage message rather than panicking.
+    fn execute_command(&mut self, bus: &mut Bus, command: &[&str]) -> String;
+}
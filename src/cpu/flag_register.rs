@@ -0,0 +1,79 @@
+const ZERO_FLAG_BYTE_POSITION: u8 = 7;
+const SUBTRACT_FLAG_BYTE_POSITION: u8 = 6;
+const HALF_CARRY_FLAG_BYTE_POSITION: u8 = 5;
+const CARRY_FLAG_BYTE_POSITION: u8 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlagRegister {
+    pub zero: bool,
+    pub subtract: bool,
+    pub half_carry: bool,
+    pub carry: bool,
+}
+
+impl Default for FlagRegister {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlagRegister {
+    pub fn new() -> Self {
+        Self {
+            zero: false,
+            subtract: false,
+            half_carry: false,
+            carry: false,
+        }
+    }
+}
+
+impl From<FlagRegister> for u8 {
+    fn from(flag: FlagRegister) -> u8 {
+        (if flag.zero { 1 } else { 0 }) << ZERO_FLAG_BYTE_POSITION
+            | (if flag.subtract { 1 } else { 0 }) << SUBTRACT_FLAG_BYTE_POSITION
+            | (if flag.half_carry { 1 } else { 0 }) << HALF_CARRY_FLAG_BYTE_POSITION
+            | (if flag.carry { 1 } else { 0 }) << CARRY_FLAG_BYTE_POSITION
+    }
+}
+
+impl From<u8> for FlagRegister {
+    fn from(byte: u8) -> Self {
+        let zero = ((byte >> ZERO_FLAG_BYTE_POSITION) & 0b1) != 0;
+        let subtract = ((byte >> SUBTRACT_FLAG_BYTE_POSITION) & 0b1) != 0;
+        let half_carry = ((byte >> HALF_CARRY_FLAG_BYTE_POSITION) & 0b1) != 0;
+        let carry = ((byte >> CARRY_FLAG_BYTE_POSITION) & 0b1) != 0;
+
+        FlagRegister {
+            zero,
+            subtract,
+            half_carry,
+            carry,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_a_flag_register_when_converting_to_u8_then_bits_are_in_the_right_position() {
+        let flags = FlagRegister {
+            zero: true,
+            subtract: false,
+            half_carry: true,
+            carry: false,
+        };
+        assert_eq!(u8::from(flags), 0b10100000);
+    }
+
+    #[test]
+    fn given_a_byte_when_converting_to_flag_register_then_fields_match_the_bit_positions() {
+        let flags = FlagRegister::from(0b10100000);
+        assert!(flags.zero);
+        assert!(!flags.subtract);
+        assert!(flags.half_carry);
+        assert!(!flags.carry);
+    }
+}
@@ -0,0 +1,85 @@
+// The memory-mapped addresses of the interrupt enable (IE) and interrupt
+// flag (IF) registers. IE lives just past the addressable RAM, IF is a
+// regular I/O register.
+pub const IE_ADDRESS: u16 = 0xFFFF;
+pub const IF_ADDRESS: u16 = 0xFF0F;
+
+// The five interrupt sources, in priority order: when more than one is
+// both enabled and pending, the earliest one in this list wins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interrupt {
+    VBlank,
+    LcdStat,
+    Timer,
+    Serial,
+    Joypad,
+}
+
+impl Interrupt {
+    const PRIORITY: [Interrupt; 5] = [
+        Interrupt::VBlank,
+        Interrupt::LcdStat,
+        Interrupt::Timer,
+        Interrupt::Serial,
+        Interrupt::Joypad,
+    ];
+
+    fn bit(self) -> u8 {
+        match self {
+            Interrupt::VBlank => 0,
+            Interrupt::LcdStat => 1,
+            Interrupt::Timer => 2,
+            Interrupt::Serial => 3,
+            Interrupt::Joypad => 4,
+        }
+    }
+
+    // Every interrupt's handler lives 8 bytes apart starting at 0x40.
+    pub fn vector(self) -> u16 {
+        0x40 + (self.bit() as u16) * 8
+    }
+
+    pub fn mask(self) -> u8 {
+        1 << self.bit()
+    }
+
+    // The highest-priority interrupt that is both enabled in `ie` and
+    // flagged pending in `iflag`, if any.
+    pub fn highest_priority(ie: u8, iflag: u8) -> Option<Interrupt> {
+        let pending = ie & iflag;
+        Self::PRIORITY
+            .iter()
+            .copied()
+            .find(|interrupt| pending & interrupt.mask() != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_several_pending_interrupts_then_the_highest_priority_one_is_picked() {
+        let ie = Interrupt::Timer.mask() | Interrupt::VBlank.mask();
+        let iflag = Interrupt::Timer.mask() | Interrupt::VBlank.mask();
+
+        assert_eq!(Interrupt::highest_priority(ie, iflag), Some(Interrupt::VBlank));
+    }
+
+    #[test]
+    fn given_a_pending_interrupt_that_is_not_enabled_then_it_is_ignored() {
+        let ie = Interrupt::VBlank.mask();
+        let iflag = Interrupt::Timer.mask();
+
+        assert_eq!(Interrupt::highest_priority(ie, iflag), None);
+    }
+
+    #[test]
+    fn each_interrupt_vector_is_eight_bytes_apart_starting_at_0x40() {
+        assert_eq!(Interrupt::VBlank.vector(), 0x40);
+        assert_eq!(Interrupt::LcdStat.vector(), 0x48);
+        assert_eq!(Interrupt::Timer.vector(), 0x50);
+        assert_eq!(Interrupt::Serial.vector(), 0x58);
+        assert_eq!(Interrupt::Joypad.vector(), 0x60);
+    }
+}
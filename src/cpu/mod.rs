@@ -0,0 +1,14 @@
+#[allow(clippy::module_inception)]
+mod cpu;
+mod debugger;
+mod flag_register;
+mod interrupt;
+mod opcodes;
+mod registers;
+
+pub use self::cpu::CPU;
+pub use self::debugger::{Debuggable, StepEvent};
+pub use self::flag_register::FlagRegister;
+pub use self::interrupt::Interrupt;
+pub use self::opcodes::{AritmeticTarget, Instruction, WordTarget};
+pub use self::registers::Registers;
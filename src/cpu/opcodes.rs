@@ -0,0 +1,62 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AritmeticTarget {
+    A,
+    B,
+    C,
+    D,
+    E,
+    H,
+    L,
+    HL,
+    D8(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WordTarget {
+    BC,
+    DE,
+    HL,
+    SP,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Instruction {
+    NOP,
+    ADD(AritmeticTarget),
+    ADC(AritmeticTarget),
+    SUB(AritmeticTarget),
+    SBC(AritmeticTarget),
+    XOR(AritmeticTarget),
+    AND(AritmeticTarget),
+    OR(AritmeticTarget),
+    CP(AritmeticTarget),
+    INC(AritmeticTarget),
+    DEC(AritmeticTarget),
+    SWAP(AritmeticTarget),
+    LD(AritmeticTarget, AritmeticTarget),
+    RLC(AritmeticTarget),
+    RRC(AritmeticTarget),
+    RL(AritmeticTarget),
+    RR(AritmeticTarget),
+    SLA(AritmeticTarget),
+    SRA(AritmeticTarget),
+    SRL(AritmeticTarget),
+    BIT(u8, AritmeticTarget),
+    SET(u8, AritmeticTarget),
+    RES(u8, AritmeticTarget),
+    // Accumulator-only rotates: unlike their CB-prefixed counterparts these
+    // always clear the zero flag instead of setting it from the result.
+    RLCA,
+    RRCA,
+    RLA,
+    RRA,
+    DAA,
+    EI,
+    DI,
+    HALT,
+    RETI,
+    ADD16(WordTarget),
+    INC16(WordTarget),
+    DEC16(WordTarget),
+    ADDSPR8(i8),
+}
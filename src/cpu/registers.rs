@@ -20,6 +20,12 @@ fn fold(left: u8, right: u8) -> u16 {
     (left as u16) << 8 | right as u16
 }
 
+impl Default for Registers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Registers {
     pub fn new() -> Self {
         Self {
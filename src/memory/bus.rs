@@ -0,0 +1,25 @@
+use memory::memory_map::MemoryMap;
+
+// The CPU's view of the full 64 KiB address space. Owns the `MemoryMap` so
+// the CPU can be handed a `&mut Bus` rather than reaching into cartridge,
+// VRAM and I/O state directly.
+#[derive(Debug)]
+pub struct Bus {
+    memory_map: MemoryMap,
+}
+
+impl Bus {
+    pub fn new(rom: Vec<u8>) -> Self {
+        Self {
+            memory_map: MemoryMap::new(rom),
+        }
+    }
+
+    pub fn read(&self, address: u16) -> u8 {
+        self.memory_map.read(address)
+    }
+
+    pub fn write(&mut self, address: u16, value: u8) {
+        self.memory_map.write(address, value);
+    }
+}
@@ -0,0 +1,172 @@
+const ROM_BANK_ZERO_START: u16 = 0x0000;
+const ROM_BANK_ZERO_END: u16 = 0x3FFF;
+const ROM_BANK_SWITCHABLE_START: u16 = 0x4000;
+const ROM_BANK_SWITCHABLE_END: u16 = 0x7FFF;
+const VRAM_START: u16 = 0x8000;
+const VRAM_END: u16 = 0x9FFF;
+const EXTERNAL_RAM_START: u16 = 0xA000;
+const EXTERNAL_RAM_END: u16 = 0xBFFF;
+const WRAM_START: u16 = 0xC000;
+const WRAM_END: u16 = 0xDFFF;
+const ECHO_RAM_START: u16 = 0xE000;
+const ECHO_RAM_END: u16 = 0xFDFF;
+const OAM_START: u16 = 0xFE00;
+const OAM_END: u16 = 0xFE9F;
+const UNUSABLE_START: u16 = 0xFEA0;
+const UNUSABLE_END: u16 = 0xFEFF;
+const IO_START: u16 = 0xFF00;
+const IO_END: u16 = 0xFF7F;
+const HRAM_START: u16 = 0xFF80;
+const HRAM_END: u16 = 0xFFFE;
+const INTERRUPT_ENABLE: u16 = 0xFFFF;
+
+const ROM_BANK_SIZE: usize = 0x4000;
+const RAM_BANK_SIZE: usize = 0x2000;
+const RAM_BANK_COUNT: usize = 4;
+
+// The bank-zero ROM, VRAM, WRAM, OAM, I/O and HRAM regions plus the MBC1
+// bank-switching registers that select which ROM/RAM bank the switchable
+// windows are mapped to. Modeled after rmg-001's `MemoryMap`, sized to the
+// full 64 KiB address space (0x10000) so the IE register at 0xFFFF is
+// addressable.
+#[derive(Debug)]
+pub struct MemoryMap {
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    vram: [u8; 0x2000],
+    wram: [u8; 0x2000],
+    oam: [u8; 0xA0],
+    io: [u8; 0x80],
+    hram: [u8; 0x7F],
+    interrupt_enable: u8,
+    ram_enabled: bool,
+    rom_bank: usize,
+    ram_bank: usize,
+}
+
+impl MemoryMap {
+    pub fn new(rom: Vec<u8>) -> Self {
+        Self {
+            rom,
+            ram: vec![0; RAM_BANK_SIZE * RAM_BANK_COUNT],
+            vram: [0; 0x2000],
+            wram: [0; 0x2000],
+            oam: [0; 0xA0],
+            io: [0; 0x80],
+            hram: [0; 0x7F],
+            interrupt_enable: 0,
+            ram_enabled: false,
+            rom_bank: 1,
+            ram_bank: 0,
+        }
+    }
+
+    pub fn read(&self, address: u16) -> u8 {
+        match address {
+            ROM_BANK_ZERO_START..=ROM_BANK_ZERO_END => {
+                self.rom.get(address as usize).copied().unwrap_or(0xFF)
+            }
+            ROM_BANK_SWITCHABLE_START..=ROM_BANK_SWITCHABLE_END => {
+                let offset = self.rom_bank * ROM_BANK_SIZE
+                    + (address - ROM_BANK_SWITCHABLE_START) as usize;
+                self.rom.get(offset).copied().unwrap_or(0xFF)
+            }
+            VRAM_START..=VRAM_END => self.vram[(address - VRAM_START) as usize],
+            EXTERNAL_RAM_START..=EXTERNAL_RAM_END => {
+                if self.ram_enabled {
+                    let offset =
+                        self.ram_bank * RAM_BANK_SIZE + (address - EXTERNAL_RAM_START) as usize;
+                    self.ram.get(offset).copied().unwrap_or(0xFF)
+                } else {
+                    0xFF
+                }
+            }
+            WRAM_START..=WRAM_END => self.wram[(address - WRAM_START) as usize],
+            ECHO_RAM_START..=ECHO_RAM_END => self.wram[(address - ECHO_RAM_START) as usize],
+            OAM_START..=OAM_END => self.oam[(address - OAM_START) as usize],
+            UNUSABLE_START..=UNUSABLE_END => 0xFF,
+            IO_START..=IO_END => self.io[(address - IO_START) as usize],
+            HRAM_START..=HRAM_END => self.hram[(address - HRAM_START) as usize],
+            INTERRUPT_ENABLE => self.interrupt_enable,
+        }
+    }
+
+    pub fn write(&mut self, address: u16, value: u8) {
+        match address {
+            // MBC1 bank-switching registers: writes into the low ROM region
+            // never touch ROM itself, they configure the switchable windows.
+            0x0000..=0x1FFF => self.ram_enabled = value & 0x0F == 0x0A,
+            0x2000..=0x3FFF => {
+                let bank = (value & 0x1F) as usize;
+                self.rom_bank = if bank == 0 { 1 } else { bank };
+            }
+            0x4000..=0x5FFF => self.ram_bank = (value & 0x03) as usize,
+            0x6000..=ROM_BANK_SWITCHABLE_END => {
+                // Banking mode select: not stored. The 0x4000-0x5FFF register
+                // always selects the RAM bank, whatever mode is written here.
+            }
+            VRAM_START..=VRAM_END => self.vram[(address - VRAM_START) as usize] = value,
+            EXTERNAL_RAM_START..=EXTERNAL_RAM_END => {
+                if self.ram_enabled {
+                    let offset =
+                        self.ram_bank * RAM_BANK_SIZE + (address - EXTERNAL_RAM_START) as usize;
+                    if let Some(slot) = self.ram.get_mut(offset) {
+                        *slot = value;
+                    }
+                }
+            }
+            WRAM_START..=WRAM_END => self.wram[(address - WRAM_START) as usize] = value,
+            ECHO_RAM_START..=ECHO_RAM_END => self.wram[(address - ECHO_RAM_START) as usize] = value,
+            OAM_START..=OAM_END => self.oam[(address - OAM_START) as usize] = value,
+            UNUSABLE_START..=UNUSABLE_END => {}
+            IO_START..=IO_END => self.io[(address - IO_START) as usize] = value,
+            HRAM_START..=HRAM_END => self.hram[(address - HRAM_START) as usize] = value,
+            INTERRUPT_ENABLE => self.interrupt_enable = value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_a_memory_map_when_the_interrupt_enable_register_is_addressed_then_it_is_readable_and_writable(
+    ) {
+        let mut memory_map = MemoryMap::new(vec![0; ROM_BANK_SIZE * 2]);
+        memory_map.write(0xFFFF, 0x1F);
+        assert_eq!(memory_map.read(0xFFFF), 0x1F);
+    }
+
+    #[test]
+    fn given_a_rom_bank_select_write_then_the_switchable_window_reads_from_that_bank() {
+        let mut rom = vec![0; ROM_BANK_SIZE * 3];
+        rom[ROM_BANK_SIZE * 2] = 0x42;
+        let mut memory_map = MemoryMap::new(rom);
+
+        memory_map.write(0x2000, 0x02);
+        assert_eq!(memory_map.read(ROM_BANK_SWITCHABLE_START), 0x42);
+    }
+
+    #[test]
+    fn given_a_rom_bank_select_of_zero_then_bank_one_is_used_instead() {
+        let mut rom = vec![0; ROM_BANK_SIZE * 2];
+        rom[ROM_BANK_SIZE] = 0x99;
+        let mut memory_map = MemoryMap::new(rom);
+
+        memory_map.write(0x2000, 0x00);
+        assert_eq!(memory_map.read(ROM_BANK_SWITCHABLE_START), 0x99);
+    }
+
+    #[test]
+    fn given_external_ram_is_disabled_then_writes_are_ignored_and_reads_return_0xff() {
+        let mut memory_map = MemoryMap::new(vec![0; ROM_BANK_SIZE * 2]);
+
+        memory_map.write(EXTERNAL_RAM_START, 0x77);
+        assert_eq!(memory_map.read(EXTERNAL_RAM_START), 0xFF);
+
+        memory_map.write(0x0000, 0x0A);
+        memory_map.write(EXTERNAL_RAM_START, 0x77);
+        assert_eq!(memory_map.read(EXTERNAL_RAM_START), 0x77);
+    }
+}
@@ -0,0 +1,6 @@
+mod bus;
+mod memory_map;
+
+pub use self::bus::Bus;
+
+pub const MEMORY_SIZE: usize = 0x10000;